@@ -6,18 +6,75 @@ use std::ffi::CStr;
 use Table;
 use {ErrorKind, MsgType, Result};
 
-
 pub type Priority = u32;
 
+/// The `netdev` family hooks are not part of `libc`, since they only apply to nftables and not
+/// to the rest of the netfilter stack. Taken from `include/uapi/linux/netfilter_netdev.h`.
+const NF_NETDEV_INGRESS: u16 = 0;
+const NF_NETDEV_EGRESS: u16 = 1;
+
 /// The netfilter event hooks a chain can register for.
+///
+/// Note that the `inet`-family hooks and the `netdev`-family hooks share the same wire values
+/// (e.g. `PreRouting` and `Ingress` are both 0), so this type cannot be a plain `#[repr(u16)]`
+/// enum with the raw hook number as its discriminant. Use [`as_raw`]/[`from_raw`] to convert to
+/// and from the wire value, which also needs the chain's family to disambiguate.
+///
+/// [`as_raw`]: #method.as_raw
+/// [`from_raw`]: #method.from_raw
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-#[repr(u16)]
 pub enum Hook {
-    PreRouting = libc::NF_INET_PRE_ROUTING as u16,
-    In = libc::NF_INET_LOCAL_IN as u16,
-    Forward = libc::NF_INET_FORWARD as u16,
-    Out = libc::NF_INET_LOCAL_OUT as u16,
-    PostRouting = libc::NF_INET_POST_ROUTING as u16,
+    PreRouting,
+    In,
+    Forward,
+    Out,
+    PostRouting,
+    /// Only valid for chains in the `netdev` family. Requires a device to be set via
+    /// [`Chain::set_device`].
+    ///
+    /// [`Chain::set_device`]: struct.Chain.html#method.set_device
+    Ingress,
+    /// Only valid for chains in the `netdev` family. Requires a device to be set via
+    /// [`Chain::set_device`].
+    ///
+    /// [`Chain::set_device`]: struct.Chain.html#method.set_device
+    Egress,
+}
+
+impl Hook {
+    /// Returns the raw wire value for this hook.
+    pub fn as_raw(self) -> u16 {
+        match self {
+            Hook::PreRouting => libc::NF_INET_PRE_ROUTING as u16,
+            Hook::In => libc::NF_INET_LOCAL_IN as u16,
+            Hook::Forward => libc::NF_INET_FORWARD as u16,
+            Hook::Out => libc::NF_INET_LOCAL_OUT as u16,
+            Hook::PostRouting => libc::NF_INET_POST_ROUTING as u16,
+            Hook::Ingress => NF_NETDEV_INGRESS,
+            Hook::Egress => NF_NETDEV_EGRESS,
+        }
+    }
+
+    /// Converts a raw wire hook number back into a `Hook`. Since the `inet` and `netdev` hook
+    /// numbers overlap, the caller must say which family `raw` was read from.
+    pub fn from_raw(is_netdev_family: bool, raw: u16) -> Option<Hook> {
+        if is_netdev_family {
+            match raw {
+                NF_NETDEV_INGRESS => Some(Hook::Ingress),
+                NF_NETDEV_EGRESS => Some(Hook::Egress),
+                _ => None,
+            }
+        } else {
+            match raw as i32 {
+                libc::NF_INET_PRE_ROUTING => Some(Hook::PreRouting),
+                libc::NF_INET_LOCAL_IN => Some(Hook::In),
+                libc::NF_INET_FORWARD => Some(Hook::Forward),
+                libc::NF_INET_LOCAL_OUT => Some(Hook::Out),
+                libc::NF_INET_POST_ROUTING => Some(Hook::PostRouting),
+                _ => None,
+            }
+        }
+    }
 }
 
 /// A chain policy. Decides what to do with a packet that was processed by the chain but did not
@@ -29,6 +86,41 @@ pub enum Policy {
     Drop = libc::NF_DROP as u32,
 }
 
+/// The type of a base chain. Determines what processing the chain's packets go through in
+/// addition to matching the chain's rules. Only meaningful for base chains, i.e. chains that
+/// have a hook set via [`set_hook`].
+///
+/// [`set_hook`]: struct.Chain.html#method.set_hook
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ChainType {
+    /// Regular packet filtering.
+    Filter,
+    /// Performs Network Address Translation. Only valid in combination with the `ip` or `ip6`
+    /// families, and only for chains hooked into prerouting, postrouting, input or output.
+    Nat,
+    /// Reroutes packets if the IP header, or any other field affecting the route, was changed.
+    Route,
+}
+
+impl ChainType {
+    fn as_c_str(self) -> &'static CStr {
+        match self {
+            ChainType::Filter => unsafe { CStr::from_bytes_with_nul_unchecked(b"filter\0") },
+            ChainType::Nat => unsafe { CStr::from_bytes_with_nul_unchecked(b"nat\0") },
+            ChainType::Route => unsafe { CStr::from_bytes_with_nul_unchecked(b"route\0") },
+        }
+    }
+
+    fn from_c_str(s: &CStr) -> Option<Self> {
+        match s.to_bytes() {
+            b"filter" => Some(ChainType::Filter),
+            b"nat" => Some(ChainType::Nat),
+            b"route" => Some(ChainType::Route),
+            _ => None,
+        }
+    }
+}
+
 /// Abstraction of a `nftnl_chain`. Chains reside inside [`Table`]s and they hold `Rule`s.
 ///
 /// There are two types of chains, "base chain" and "regular chain". See [`set_hook`] for more
@@ -60,6 +152,37 @@ impl<'a> Chain<'a> {
         }
     }
 
+    /// Parses a chain out of a `NFT_MSG_NEWCHAIN`/`NFT_MSG_GETCHAIN` netlink reply, such as the
+    /// ones returned when listing the chains in `table` with [`get_chains_nlmsg`]. Returns
+    /// `None` if the message belongs to a chain in a different table than `table` (this can
+    /// happen since several tables can share a family, e.g. `ip filter` and `ip nat` are both
+    /// `NFPROTO_IPV4`), or an error if the message could not be parsed as a chain at all.
+    ///
+    /// [`get_chains_nlmsg`]: fn.get_chains_nlmsg.html
+    pub fn from_nlmsg(header: &libc::nlmsghdr, table: &'a Table) -> Result<Option<Chain<'a>>> {
+        unsafe {
+            let chain = sys::nftnl_chain_alloc();
+            ensure!(!chain.is_null(), ErrorKind::AllocationError);
+
+            let err = sys::nftnl_chain_nlmsg_parse(header, chain);
+            if err < 0 {
+                sys::nftnl_chain_free(chain);
+                return Err(ErrorKind::ParseError.into());
+            }
+
+            let chain_table = CStr::from_ptr(sys::nftnl_chain_get_str(
+                chain,
+                sys::NFTNL_CHAIN_TABLE as u16,
+            ));
+            if chain_table != table.get_name() {
+                sys::nftnl_chain_free(chain);
+                return Ok(None);
+            }
+
+            Ok(Some(Chain { chain, table }))
+        }
+    }
+
     /// Sets the hook and priority for this chain. Without calling this method the chain well
     /// become a "regular chain" without any hook and will thus not receive any traffic unless
     /// some rule forward packets to it via goto or jump verdicts.
@@ -69,17 +192,65 @@ impl<'a> Chain<'a> {
     /// networking stack.
     pub fn set_hook(&mut self, hook: Hook, priority: Priority) {
         unsafe {
-            sys::nftnl_chain_set_u32(self.chain, sys::NFTNL_CHAIN_HOOKNUM as u16, hook as u32);
+            sys::nftnl_chain_set_u32(
+                self.chain,
+                sys::NFTNL_CHAIN_HOOKNUM as u16,
+                hook.as_raw() as u32,
+            );
             sys::nftnl_chain_set_u32(self.chain, sys::NFTNL_CHAIN_PRIO as u16, priority);
         }
     }
 
+    /// Sets the type of this chain. The type affects what the chain's packets go through on top
+    /// of matching the chain's rules, e.g. `Nat` lets the chain's rules perform NAT and `Route`
+    /// makes the kernel re-check the route of a packet if its IP header changed. Only meaningful
+    /// for base chains, see [`set_hook`].
+    ///
+    /// [`set_hook`]: #method.set_hook
+    pub fn set_type(&mut self, chain_type: ChainType) {
+        unsafe {
+            sys::nftnl_chain_set_str(
+                self.chain,
+                sys::NFTNL_CHAIN_TYPE as u16,
+                chain_type.as_c_str().as_ptr(),
+            );
+        }
+    }
+
     pub fn set_policy(&mut self, policy: Policy) {
         unsafe {
             sys::nftnl_chain_set_u32(self.chain, sys::NFTNL_CHAIN_POLICY as u16, policy as u32);
         }
     }
 
+    /// Assigns a transaction-local numeric id to this chain. This id is only meaningful within
+    /// the same [`Batch`] and lets other messages in that batch, such as rules with jump/goto
+    /// verdicts, reference this chain before it has been committed to the kernel and thus before
+    /// it has a real handle. The caller is responsible for picking an id that is unique within
+    /// the batch.
+    ///
+    /// [`Batch`]: struct.Batch.html
+    pub fn set_id(&mut self, id: u32) {
+        unsafe {
+            sys::nftnl_chain_set_u32(self.chain, sys::NFTNL_CHAIN_ID as u16, id);
+        }
+    }
+
+    /// Sets the device this chain is attached to. Only valid, and mandatory, for base chains in
+    /// the `netdev` family, i.e. chains hooked to [`Hook::Ingress`] or [`Hook::Egress`].
+    ///
+    /// [`Hook::Ingress`]: enum.Hook.html#variant.Ingress
+    /// [`Hook::Egress`]: enum.Hook.html#variant.Egress
+    pub fn set_device<T: AsRef<CStr>>(&mut self, iface: &T) {
+        unsafe {
+            sys::nftnl_chain_set_str(
+                self.chain,
+                sys::NFTNL_CHAIN_DEV as u16,
+                iface.as_ref().as_ptr(),
+            );
+        }
+    }
+
     pub fn get_name(&self) -> &CStr {
         unsafe {
             let ptr = sys::nftnl_chain_get_str(self.chain, sys::NFTNL_CHAIN_NAME as u16);
@@ -90,6 +261,78 @@ impl<'a> Chain<'a> {
     pub fn get_table(&self) -> &Table {
         self.table
     }
+
+    /// Returns the hook and priority this chain is registered with, if any. A chain with no
+    /// hook set is a "regular chain", see [`set_hook`]. The raw hook number is disambiguated
+    /// using the chain's table family, since the `inet` and `netdev` hook numbers overlap.
+    ///
+    /// [`set_hook`]: #method.set_hook
+    pub fn get_hook(&self) -> Option<(Hook, Priority)> {
+        unsafe {
+            if !sys::nftnl_chain_is_set(self.chain, sys::NFTNL_CHAIN_HOOKNUM as u16) {
+                return None;
+            }
+            let hooknum = sys::nftnl_chain_get_u32(self.chain, sys::NFTNL_CHAIN_HOOKNUM as u16);
+            let priority = sys::nftnl_chain_get_u32(self.chain, sys::NFTNL_CHAIN_PRIO as u16);
+            let is_netdev_family = self.table.get_family() as u16 == libc::NFPROTO_NETDEV as u16;
+            let hook = Hook::from_raw(is_netdev_family, hooknum as u16)?;
+            Some((hook, priority))
+        }
+    }
+
+    /// Returns the policy of this chain, if one has been set.
+    pub fn get_policy(&self) -> Option<Policy> {
+        unsafe {
+            if !sys::nftnl_chain_is_set(self.chain, sys::NFTNL_CHAIN_POLICY as u16) {
+                return None;
+            }
+            match sys::nftnl_chain_get_u32(self.chain, sys::NFTNL_CHAIN_POLICY as u16) as i32 {
+                p if p == Policy::Accept as i32 => Some(Policy::Accept),
+                p if p == Policy::Drop as i32 => Some(Policy::Drop),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns the type of this chain, if one has been set.
+    pub fn get_type(&self) -> Option<ChainType> {
+        unsafe {
+            if !sys::nftnl_chain_is_set(self.chain, sys::NFTNL_CHAIN_TYPE as u16) {
+                return None;
+            }
+            let ptr = sys::nftnl_chain_get_str(self.chain, sys::NFTNL_CHAIN_TYPE as u16);
+            ChainType::from_c_str(CStr::from_ptr(ptr))
+        }
+    }
+}
+
+/// Builds a `NFT_MSG_GETCHAIN` dump request for the chains in `table`'s family, carrying
+/// `table`'s name in its payload. The response is a dump of `NFT_MSG_NEWCHAIN` messages, which
+/// can be turned into [`Chain`]s via [`Chain::from_nlmsg`]; that returns `None` for a chain that
+/// belongs to another table of the same family.
+///
+/// [`Chain`]: struct.Chain.html
+/// [`Chain::from_nlmsg`]: struct.Chain.html#method.from_nlmsg
+pub unsafe fn get_chains_nlmsg(table: &Table, buf: *mut c_void, seq: u32) -> Result<()> {
+    let header = sys::nftnl_nlmsg_build_hdr(
+        buf as *mut i8,
+        libc::NFT_MSG_GETCHAIN as u16,
+        table.get_family() as u16,
+        (libc::NLM_F_ACK | libc::NLM_F_DUMP) as u16,
+        seq,
+    );
+
+    let filter = sys::nftnl_chain_alloc();
+    ensure!(!filter.is_null(), ErrorKind::AllocationError);
+    sys::nftnl_chain_set_str(
+        filter,
+        sys::NFTNL_CHAIN_TABLE as u16,
+        table.get_name().as_ptr(),
+    );
+    sys::nftnl_chain_nlmsg_build_payload(header, filter);
+    sys::nftnl_chain_free(filter);
+
+    Ok(())
 }
 
 unsafe impl<'a> ::NlMsg for Chain<'a> {
@@ -113,4 +356,4 @@ impl<'a> Drop for Chain<'a> {
     fn drop(&mut self) {
         unsafe { sys::nftnl_chain_free(self.chain) };
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,16 @@
+//! Low level building blocks that can be added to a `Rule` to match packets and decide what to
+//! do with them.
+
+mod verdict;
+pub use self::verdict::{ChainRef, Verdict};
+
+use nftnl_sys as sys;
+
+use Result;
+
+/// A type that can produce the low level `nftnl_expr` representation of itself, so it can be
+/// added to a rule's expression list.
+pub unsafe trait Expression {
+    /// Allocates and returns the low level `nftnl_expr` representation of this expression.
+    fn to_expr(&self) -> Result<*mut sys::nftnl_expr>;
+}
@@ -0,0 +1,96 @@
+use libc;
+use nftnl_sys::{self as sys};
+
+use std::ffi::CStr;
+
+use super::Expression;
+use {ErrorKind, Result};
+
+/// The nf_tables verdicts are not part of `libc`, since they only apply to nftables and share
+/// their numeric space with, but are distinct from, the netfilter verdicts in `libc::NF_*`.
+/// Taken from `include/uapi/linux/netfilter/nf_tables.h`.
+const NFT_CONTINUE: i32 = -1;
+const NFT_RETURN: i32 = -5;
+const NFT_JUMP: i32 = -3;
+const NFT_GOTO: i32 = -4;
+const NFT_REG_VERDICT: u32 = 0;
+
+/// Identifies the chain a [`Verdict::Jump`] or [`Verdict::Goto`] targets.
+///
+/// A chain can be targeted either by name, which requires the chain to already exist in the
+/// kernel, or by the transaction-local id assigned to it via [`Chain::set_id`]. The latter is
+/// what makes it possible to atomically create a chain and add a rule that jumps or gotos into
+/// it within the same [`Batch`], since the chain has no real handle to look up by name or handle
+/// until the batch is committed.
+///
+/// [`Verdict::Jump`]: enum.Verdict.html#variant.Jump
+/// [`Verdict::Goto`]: enum.Verdict.html#variant.Goto
+/// [`Chain::set_id`]: ../struct.Chain.html#method.set_id
+/// [`Batch`]: ../struct.Batch.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ChainRef<'a> {
+    /// Targets a chain that already exists in the kernel, by name.
+    Name(&'a CStr),
+    /// Targets a chain created earlier in the same batch, by the id given to it via
+    /// `Chain::set_id`.
+    Id(u32),
+}
+
+/// A verdict a rule can issue on a packet that matches it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Verdict<'a> {
+    /// Silently let the packet through.
+    Accept,
+    /// Silently drop the packet.
+    Drop,
+    /// Stop evaluating the current rule and continue with the next rule in the chain.
+    Continue,
+    /// Stop evaluating the current chain and return to the one that called it, if any.
+    Return,
+    /// Push the current chain on the jump stack and jump to the given chain.
+    Jump(ChainRef<'a>),
+    /// Jump to the given chain without pushing the current chain on the jump stack.
+    Goto(ChainRef<'a>),
+}
+
+impl<'a> Verdict<'a> {
+    fn code(&self) -> i32 {
+        match *self {
+            Verdict::Accept => libc::NF_ACCEPT,
+            Verdict::Drop => libc::NF_DROP,
+            Verdict::Continue => NFT_CONTINUE,
+            Verdict::Return => NFT_RETURN,
+            Verdict::Jump(..) => NFT_JUMP,
+            Verdict::Goto(..) => NFT_GOTO,
+        }
+    }
+
+    fn chain(&self) -> Option<&ChainRef<'a>> {
+        match *self {
+            Verdict::Jump(ref chain) | Verdict::Goto(ref chain) => Some(chain),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl<'a> Expression for Verdict<'a> {
+    fn to_expr(&self) -> Result<*mut sys::nftnl_expr> {
+        unsafe {
+            let expr = sys::nftnl_expr_alloc(b"immediate\0".as_ptr() as *const _);
+            ensure!(!expr.is_null(), ErrorKind::AllocationError);
+
+            sys::nftnl_expr_set_u32(expr, sys::NFTNL_EXPR_IMM_DREG as u16, NFT_REG_VERDICT);
+            sys::nftnl_expr_set_u32(expr, sys::NFTNL_EXPR_IMM_VERDICT as u16, self.code() as u32);
+            match self.chain() {
+                Some(ChainRef::Name(name)) => {
+                    sys::nftnl_expr_set_str(expr, sys::NFTNL_EXPR_IMM_CHAIN as u16, name.as_ptr());
+                }
+                Some(ChainRef::Id(id)) => {
+                    sys::nftnl_expr_set_u32(expr, sys::NFTNL_EXPR_IMM_CHAIN_ID as u16, *id);
+                }
+                None => {}
+            }
+            Ok(expr)
+        }
+    }
+}